@@ -2,6 +2,7 @@
 #![allow(unused_imports)]
 
 use std::fmt::{Debug, Display, Formatter};
+use std::io::Read;
 use std::str::FromStr;
 use anyhow::anyhow;
 use crate::{Error, Result};
@@ -21,6 +22,8 @@ pub struct Chunk {
 enum ChunkErr {
     ParseErr,
     CrcVerify,
+    UnexpectedEof,
+    LengthOverflow,
 }
 
 
@@ -29,6 +32,8 @@ impl Display for ChunkErr {
         match *self {
             ChunkErr::ParseErr => write!(f, "parse err"),
             ChunkErr::CrcVerify => write!(f, "verify crc"),
+            ChunkErr::UnexpectedEof => write!(f, "unexpected eof before chunk was complete"),
+            ChunkErr::LengthOverflow => write!(f, "chunk length exceeds the 2^31-1 maximum allowed by the PNG spec"),
         }
     }
 }
@@ -75,11 +80,66 @@ impl TryFrom<&Vec<u8>> for Chunk {
     }
 }
 
+/// The PNG spec (section 3.2) caps a chunk's length at 2^31-1; `from_reader` rejects any larger
+/// length field before allocating `chunk_data`, so the length always fits in a 32-bit `usize`.
+/// Lengths up to the cap are accepted and allocated in full, which can still be close to 2 GiB.
+const MAX_CHUNK_LENGTH: u32 = (1 << 31) - 1;
+
+impl Chunk {
+    /// Reads a single chunk (length, type, data, crc) from `r`, verifying the CRC as it goes.
+    /// Unlike `Chunk::try_from`, this never needs the whole file buffered up front.
+    pub fn from_reader<R: Read>(r: &mut R) -> Result<Chunk> {
+        Self::from_reader_impl(r, true)
+    }
+
+    /// Like `from_reader`, but a CRC mismatch is recorded on the returned `Chunk` instead of
+    /// aborting the read. Used by `Png::validate` so a corrupt chunk doesn't prevent every other
+    /// structural rule from being checked too.
+    pub fn from_reader_lenient<R: Read>(r: &mut R) -> Result<Chunk> {
+        Self::from_reader_impl(r, false)
+    }
+
+    fn from_reader_impl<R: Read>(r: &mut R, verify_crc: bool) -> Result<Chunk> {
+        let mut length_buf = [0u8; 4];
+        r.read_exact(&mut length_buf).map_err(|_| ChunkErr::UnexpectedEof)?;
+        let length = u32::from_be_bytes(length_buf);
+        if length > MAX_CHUNK_LENGTH {
+            return Err(Box::from(ChunkErr::LengthOverflow));
+        }
+        let length = length as usize;
+
+        let mut type_buf = [0u8; 4];
+        r.read_exact(&mut type_buf).map_err(|_| ChunkErr::UnexpectedEof)?;
+        let chunk_type_str = String::from_utf8(type_buf.to_vec())
+            .map_err(|_| ("chunk_type is invalid"))?;
+        let chunk_type = ChunkType::from_str(&chunk_type_str)?;
+
+        let mut chunk_data = vec![0u8; length];
+        r.read_exact(&mut chunk_data).map_err(|_| ChunkErr::UnexpectedEof)?;
+
+        let mut crc_buf = [0u8; 4];
+        r.read_exact(&mut crc_buf).map_err(|_| ChunkErr::UnexpectedEof)?;
+        let crc = u32::from_be_bytes(crc_buf);
+
+        let checksum = crc_checksum(&chunk_type, &chunk_data);
+        if verify_crc && checksum != crc {
+            return Err(Box::from(ChunkErr::CrcVerify));
+        }
+
+        Ok(Chunk {
+            length: length as u32,
+            chunk_type,
+            chunk_data,
+            crc,
+        })
+    }
+}
+
 fn crc_checksum(chunk_type: &ChunkType, chunk_data: &Vec<u8>) -> u32 {
-    // let data = chunk_type.bytes().iter().chain(chunk_data.as_slice().iter()).copied().collect::<Vec<u8>>();
-    let mut data = chunk_type.bytes().to_vec();
-    data.append(&mut chunk_data.to_vec());
-    crc::crc32::checksum_ieee(&data)
+    let mut crc = crate::crc32::Crc32::new();
+    crc.update(&chunk_type.bytes());
+    crc.update(chunk_data);
+    crc.finalize()
 }
 
 impl Display for Chunk {
@@ -109,9 +169,13 @@ impl Chunk {
     pub fn chunk_type(&self) -> &ChunkType {
         &self.chunk_type
     }
-    fn data(&self) -> &[u8] {
+    pub fn data(&self) -> &[u8] {
         &self.chunk_data
     }
+    /// Recomputes the CRC over the chunk's type and data and compares it against the stored one.
+    pub fn is_crc_valid(&self) -> bool {
+        crc_checksum(&self.chunk_type, &self.chunk_data) == self.crc
+    }
     fn crc(&self) -> u32 {
         self.crc
     }
@@ -262,4 +326,76 @@ mod tests {
 
         let _chunk_string = format!("{}", chunk);
     }
+
+    fn testing_chunk_bytes() -> Vec<u8> {
+        let data_length: u32 = 42;
+        let chunk_type = "RuSt".as_bytes();
+        let message_bytes = "This is where your secret message will be!".as_bytes();
+        let crc: u32 = 2882656334;
+
+        data_length
+            .to_be_bytes()
+            .iter()
+            .chain(chunk_type.iter())
+            .chain(message_bytes.iter())
+            .chain(crc.to_be_bytes().iter())
+            .copied()
+            .collect()
+    }
+
+    #[test]
+    fn test_chunk_from_reader_round_trip() {
+        let bytes = testing_chunk_bytes();
+        let mut cursor = std::io::Cursor::new(bytes);
+
+        let chunk = Chunk::from_reader(&mut cursor).unwrap();
+
+        assert_eq!(chunk.length(), 42);
+        assert_eq!(chunk.chunk_type().to_string(), String::from("RuSt"));
+        assert_eq!(chunk.crc(), 2882656334);
+        assert_eq!(
+            chunk.data_as_string().unwrap(),
+            String::from("This is where your secret message will be!")
+        );
+    }
+
+    #[test]
+    fn test_chunk_from_reader_crc_mismatch() {
+        let mut bytes = testing_chunk_bytes();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0xFF;
+        let mut cursor = std::io::Cursor::new(bytes);
+
+        assert!(Chunk::from_reader(&mut cursor).is_err());
+    }
+
+    #[test]
+    fn test_chunk_from_reader_unexpected_eof_mid_chunk() {
+        let bytes = testing_chunk_bytes();
+        let truncated = &bytes[..bytes.len() - 10];
+        let mut cursor = std::io::Cursor::new(truncated.to_vec());
+
+        assert!(Chunk::from_reader(&mut cursor).is_err());
+    }
+
+    #[test]
+    fn test_chunk_from_reader_length_overflow() {
+        let mut bytes = (MAX_CHUNK_LENGTH + 1).to_be_bytes().to_vec();
+        bytes.extend_from_slice("RuSt".as_bytes());
+        let mut cursor = std::io::Cursor::new(bytes);
+
+        assert!(Chunk::from_reader(&mut cursor).is_err());
+    }
+
+    #[test]
+    fn test_chunk_from_reader_lenient_keeps_crc_mismatch() {
+        let mut bytes = testing_chunk_bytes();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0xFF;
+        let mut cursor = std::io::Cursor::new(bytes);
+
+        let chunk = Chunk::from_reader_lenient(&mut cursor).unwrap();
+
+        assert!(!chunk.is_crc_valid());
+    }
 }
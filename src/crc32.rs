@@ -0,0 +1,95 @@
+#![allow(unused_imports)]
+
+/// Reflected CRC-32 polynomial used by PNG (ITU-T V.42 / zlib), per spec appendix D.
+const POLY: u32 = 0xEDB88320;
+
+const fn build_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut n = 0;
+    while n < 256 {
+        let mut c = n as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 != 0 { POLY ^ (c >> 1) } else { c >> 1 };
+            k += 1;
+        }
+        table[n] = c;
+        n += 1;
+    }
+    table
+}
+
+static CRC32_TABLE: [u32; 256] = build_table();
+
+/// An incremental CRC-32 accumulator, fed in the same byte-at-a-time fashion the PNG spec
+/// describes, so a `Chunk`'s CRC can be built up as its type and data are assembled instead of
+/// recomputed from scratch every time.
+#[derive(Debug, Clone, Copy)]
+pub struct Crc32 {
+    state: u32,
+}
+
+impl Crc32 {
+    pub fn new() -> Crc32 {
+        Crc32 { state: 0xFFFFFFFF }
+    }
+
+    pub fn update(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.state = CRC32_TABLE[((self.state ^ byte as u32) & 0xFF) as usize] ^ (self.state >> 8);
+        }
+    }
+
+    pub fn finalize(self) -> u32 {
+        self.state ^ 0xFFFFFFFF
+    }
+}
+
+impl Default for Crc32 {
+    fn default() -> Self {
+        Crc32::new()
+    }
+}
+
+/// One-shot convenience wrapper around `Crc32` for callers that already have the whole buffer.
+pub fn checksum(bytes: &[u8]) -> u32 {
+    let mut crc = Crc32::new();
+    crc.update(bytes);
+    crc.finalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checksum_matches_known_png_test_value() {
+        let chunk_type = "RuSt".as_bytes();
+        let message = "This is where your secret message will be!".as_bytes();
+
+        let mut crc = Crc32::new();
+        crc.update(chunk_type);
+        crc.update(message);
+
+        assert_eq!(crc.finalize(), 2882656334);
+    }
+
+    #[test]
+    fn test_checksum_one_shot_matches_incremental() {
+        let data = [chunk_type_and_message()].concat();
+        let mut incremental = Crc32::new();
+        incremental.update(&data[..4]);
+        incremental.update(&data[4..]);
+        assert_eq!(checksum(&data), incremental.finalize());
+        assert_eq!(checksum(&data), 2882656334);
+    }
+
+    fn chunk_type_and_message() -> Vec<u8> {
+        "RuSt"
+            .as_bytes()
+            .iter()
+            .chain("This is where your secret message will be!".as_bytes().iter())
+            .copied()
+            .collect()
+    }
+}
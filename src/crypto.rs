@@ -0,0 +1,81 @@
+#![allow(unused_imports)]
+
+use chacha20poly1305::aead::{Aead, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use chacha20poly1305::aead::rand_core::RngCore;
+use argon2::Argon2;
+use crate::{Error, Result};
+
+pub const SALT_LEN: usize = 16;
+pub const NONCE_LEN: usize = 12;
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<[u8; 32]> {
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|_| Error::from("key derivation failed"))?;
+    Ok(key)
+}
+
+/// Encrypts `message` with a key derived from `passphrase`, laying out the result as
+/// `salt (16 bytes) || nonce (12 bytes) || ciphertext+tag` so decryption can recover the salt
+/// and nonce without any side channel.
+pub fn encrypt(message: &[u8], passphrase: &str) -> Result<Vec<u8>> {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let key = derive_key(passphrase, &salt)?;
+
+    let mut nonce_bytes = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce_bytes);
+    let nonce = Nonce::from_slice(&nonce_bytes);
+
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(&key));
+    let ciphertext = cipher
+        .encrypt(nonce, message)
+        .map_err(|_| Error::from("encryption failed"))?;
+
+    Ok(salt
+        .iter()
+        .chain(nonce_bytes.iter())
+        .chain(ciphertext.iter())
+        .copied()
+        .collect())
+}
+
+/// Reverses `encrypt`. Fails with an authentication error rather than returning garbage if
+/// `passphrase` is wrong or `payload` has been tampered with.
+pub fn decrypt(payload: &[u8], passphrase: &str) -> Result<Vec<u8>> {
+    if payload.len() < SALT_LEN + NONCE_LEN {
+        return Err(Error::from("encrypted payload is too short"));
+    }
+    let (salt, rest) = payload.split_at(SALT_LEN);
+    let (nonce_bytes, ciphertext) = rest.split_at(NONCE_LEN);
+
+    let key = derive_key(passphrase, salt)?;
+    let nonce = Nonce::from_slice(nonce_bytes);
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(&key));
+
+    cipher
+        .decrypt(nonce, ciphertext)
+        .map_err(|_| Error::from("decryption failed: wrong key or corrupted data"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_decrypt_roundtrip() {
+        let message = b"This is where your secret message will be!";
+        let encrypted = encrypt(message, "correct horse battery staple").unwrap();
+        let decrypted = decrypt(&encrypted, "correct horse battery staple").unwrap();
+        assert_eq!(decrypted, message);
+    }
+
+    #[test]
+    fn test_decrypt_wrong_key_fails() {
+        let message = b"This is where your secret message will be!";
+        let encrypted = encrypt(message, "correct horse battery staple").unwrap();
+        assert!(decrypt(&encrypted, "wrong passphrase").is_err());
+    }
+}
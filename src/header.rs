@@ -0,0 +1,121 @@
+#![allow(unused_imports)]
+
+use crate::{Error, Result};
+
+pub const IHDR_DATA_LENGTH: usize = 13;
+
+/// The parsed payload of an `IHDR` chunk: PNG's image-level metadata (spec section 11.2.2).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Header {
+    width: u32,
+    height: u32,
+    bit_depth: u8,
+    color_type: u8,
+    compression_method: u8,
+    filter_method: u8,
+    interlace_method: u8,
+}
+
+impl Header {
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+    pub fn bit_depth(&self) -> u8 {
+        self.bit_depth
+    }
+    pub fn color_type(&self) -> u8 {
+        self.color_type
+    }
+    pub fn compression_method(&self) -> u8 {
+        self.compression_method
+    }
+    pub fn filter_method(&self) -> u8 {
+        self.filter_method
+    }
+    pub fn interlace_method(&self) -> u8 {
+        self.interlace_method
+    }
+
+    /// Number of channels implied by `color_type`, per the PNG spec (table 11.5).
+    pub fn channels(&self) -> Result<u8> {
+        match self.color_type {
+            0 => Ok(1), // grayscale
+            2 => Ok(3), // truecolor
+            3 => Ok(1), // indexed-color
+            4 => Ok(2), // grayscale + alpha
+            6 => Ok(4), // truecolor + alpha
+            _ => Err(Error::from("unknown color type")),
+        }
+    }
+
+    /// Human-readable name of `color_type`, per the PNG spec (table 11.5).
+    pub fn color_type_name(&self) -> Result<&'static str> {
+        match self.color_type {
+            0 => Ok("grayscale"),
+            2 => Ok("RGB"),
+            3 => Ok("indexed"),
+            4 => Ok("grayscale+alpha"),
+            6 => Ok("RGBA"),
+            _ => Err(Error::from("unknown color type")),
+        }
+    }
+}
+
+impl TryFrom<&[u8]> for Header {
+    type Error = Error;
+
+    fn try_from(data: &[u8]) -> Result<Self> {
+        if data.len() != IHDR_DATA_LENGTH {
+            return Err(Error::from("IHDR data must be exactly 13 bytes"));
+        }
+
+        Ok(Header {
+            width: u32::from_be_bytes(data[0..4].try_into().unwrap()),
+            height: u32::from_be_bytes(data[4..8].try_into().unwrap()),
+            bit_depth: data[8],
+            color_type: data[9],
+            compression_method: data[10],
+            filter_method: data[11],
+            interlace_method: data[12],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn testing_ihdr_data() -> Vec<u8> {
+        640u32
+            .to_be_bytes()
+            .iter()
+            .chain(480u32.to_be_bytes().iter())
+            .chain([8u8, 6, 0, 0, 0].iter())
+            .copied()
+            .collect()
+    }
+
+    #[test]
+    fn test_header_from_ihdr_data() {
+        let header = Header::try_from(testing_ihdr_data().as_slice()).unwrap();
+        assert_eq!(header.width(), 640);
+        assert_eq!(header.height(), 480);
+        assert_eq!(header.bit_depth(), 8);
+        assert_eq!(header.color_type(), 6);
+        assert_eq!(header.channels().unwrap(), 4);
+    }
+
+    #[test]
+    fn test_header_rejects_wrong_length() {
+        assert!(Header::try_from([0u8; 12].as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_header_color_type_name() {
+        let header = Header::try_from(testing_ihdr_data().as_slice()).unwrap();
+        assert_eq!(header.color_type_name().unwrap(), "RGBA");
+    }
+}
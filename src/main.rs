@@ -7,11 +7,13 @@ use crate::chunk::Chunk;
 use crate::chunk_type::ChunkType;
 use crate::png::Png;
 
-mod args;
 mod chunk;
 mod chunk_type;
-mod commands;
+mod crc32;
+mod crypto;
+mod header;
 mod png;
+mod validate;
 
 pub type Error = Box<dyn std::error::Error>;
 pub type Result<T> = std::result::Result<T, Error>;
@@ -26,6 +28,10 @@ pngme remove ./dice.png ruSt
 
 pngme print ./dice.png
 
+pngme validate ./dice.png
+
+pngme info ./dice.png
+
  */
 
 fn main() -> Result<()> {
@@ -40,6 +46,9 @@ fn main() -> Result<()> {
         .arg(
             arg!([message] "message").default_missing_value("")
         )
+        .arg(
+            arg!(-k --key <key> "passphrase to encrypt/decrypt the secret message").required(false)
+        )
         .get_matches();
 
     let mut action;
@@ -65,19 +74,36 @@ fn main() -> Result<()> {
         message = (*v).clone();
     }
 
+    let key = matches.get_one::<std::string::String>("key").cloned();
+
     println!("{} {} {} {}",action, path, chunk_type, message);
-    let png_vec= std::fs::read(&path).unwrap();
-    let png_bytes = png_vec.as_slice();
-    let mut png = Png::try_from(png_bytes).unwrap();
+    let mut png_file = std::fs::File::open(&path)?;
+    // `validate` must still be able to report a CRC mismatch as one violation among others, so it
+    // parses leniently instead of aborting on the first bad chunk like every other action does.
+    let mut png = if action == "validate" {
+        Png::from_reader_lenient(&mut png_file)?
+    } else {
+        Png::from_reader(&mut png_file)?
+    };
     match action.as_str() {
         "encode" => {
-            let chunk = Chunk::new(ChunkType::from_str(&chunk_type)?, message.as_bytes().to_vec());
+            let payload = match &key {
+                Some(passphrase) => crypto::encrypt(message.as_bytes(), passphrase)?,
+                None => message.as_bytes().to_vec(),
+            };
+            let chunk = Chunk::new(ChunkType::from_str(&chunk_type)?, payload);
             png.append_chunk(chunk);
             std::fs::write(path, png.as_bytes()).expect("write failed")
         }
         "decode" => {
             if let Some(chunk) = png.chunk_by_type(&chunk_type) {
-                println!("{}", chunk);
+                match &key {
+                    Some(passphrase) => {
+                        let plaintext = crypto::decrypt(chunk.data(), passphrase)?;
+                        println!("{}", std::string::String::from_utf8_lossy(&plaintext));
+                    }
+                    None => println!("{}", chunk),
+                }
             } else {
                 println!("no chunk found")
             }
@@ -93,6 +119,27 @@ fn main() -> Result<()> {
                 println!("{}", v)
             });
         }
+        "validate" => {
+            let errors = png.validate();
+            if errors.is_empty() {
+                println!("valid PNG");
+            } else {
+                errors.iter().for_each(|e| println!("{}", e));
+            }
+        }
+        "info" => {
+            let header = png.header()?;
+            println!(
+                "{}x{}, {}-bit {}",
+                header.width(),
+                header.height(),
+                header.bit_depth(),
+                header.color_type_name()?
+            );
+            if let Ok(image_data) = png.image_data() {
+                println!("{} bytes of decoded image data", image_data.len());
+            }
+        }
         _ => {
             panic!("unknown action")
         }
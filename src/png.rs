@@ -0,0 +1,527 @@
+#![allow(unused_variables)]
+#![allow(unused_imports)]
+
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+use flate2::read::ZlibDecoder;
+use crate::{Error, Result};
+use crate::chunk::Chunk;
+use crate::header::Header;
+use crate::validate::ValidationError;
+
+#[derive(Debug)]
+enum PngErr {
+    InvalidSignature,
+}
+
+impl Display for PngErr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            PngErr::InvalidSignature => write!(f, "invalid png signature"),
+        }
+    }
+}
+
+impl std::error::Error for PngErr {}
+
+pub struct Png {
+    chunks: Vec<Chunk>,
+}
+
+impl Png {
+    pub const STANDARD_HEADER: [u8; 8] = [137, 80, 78, 71, 13, 10, 26, 10];
+
+    pub fn from_chunks(chunks: Vec<Chunk>) -> Png {
+        Png { chunks }
+    }
+
+    /// Parses a PNG incrementally from any `Read` source, verifying each chunk's CRC as it is
+    /// read instead of requiring the whole file to be buffered up front.
+    pub fn from_reader<R: Read>(r: &mut R) -> Result<Png> {
+        Self::from_reader_impl(r, Chunk::from_reader)
+    }
+
+    /// Like `from_reader`, but a chunk whose CRC doesn't match is kept rather than aborting the
+    /// parse. Intended for `validate`, which needs to report a CRC mismatch as one violation
+    /// alongside whatever else is wrong with the file instead of stopping at the first one.
+    pub fn from_reader_lenient<R: Read>(r: &mut R) -> Result<Png> {
+        Self::from_reader_impl(r, Chunk::from_reader_lenient)
+    }
+
+    fn from_reader_impl<R: Read>(
+        r: &mut R,
+        read_chunk: fn(&mut R) -> Result<Chunk>,
+    ) -> Result<Png> {
+        let mut signature = [0u8; 8];
+        r.read_exact(&mut signature)
+            .map_err(|_| Error::from("unexpected eof while reading png signature"))?;
+        if signature != Self::STANDARD_HEADER {
+            return Err(Error::from(PngErr::InvalidSignature));
+        }
+
+        let mut chunks = Vec::new();
+        loop {
+            // `Chunk::from_reader`/`from_reader_lenient` already turn a truncated chunk into
+            // `ChunkErr::UnexpectedEof`, so no further mapping is needed here.
+            let chunk = read_chunk(r)?;
+            let is_iend = chunk.chunk_type().to_string() == "IEND";
+            chunks.push(chunk);
+            if is_iend {
+                break;
+            }
+        }
+
+        Ok(Png { chunks })
+    }
+
+    pub fn append_chunk(&mut self, chunk: Chunk) {
+        self.chunks.push(chunk);
+    }
+
+    pub fn remove_chunk(&mut self, chunk_type: &str) -> Result<Chunk> {
+        let pos = self
+            .chunks
+            .iter()
+            .position(|c| c.chunk_type().to_string() == chunk_type)
+            .ok_or_else(|| Error::from("chunk not found"))?;
+        Ok(self.chunks.remove(pos))
+    }
+
+    pub fn chunks(&self) -> &[Chunk] {
+        &self.chunks
+    }
+
+    pub fn chunk_by_type(&self, chunk_type: &str) -> Option<&Chunk> {
+        self.chunks.iter().find(|c| c.chunk_type().to_string() == chunk_type)
+    }
+
+    pub fn as_bytes(&self) -> Vec<u8> {
+        Self::STANDARD_HEADER
+            .iter()
+            .chain(self.chunks.iter().flat_map(|c| c.as_bytes()).collect::<Vec<u8>>().iter())
+            .copied()
+            .collect()
+    }
+
+    /// Parses the `IHDR` chunk into width/height/bit-depth/color-type metadata.
+    pub fn header(&self) -> Result<Header> {
+        let ihdr = self
+            .chunk_by_type("IHDR")
+            .ok_or_else(|| Error::from("missing IHDR chunk"))?;
+        Header::try_from(ihdr.data())
+    }
+
+    /// Concatenates every `IDAT` chunk's payload, in order, and inflates it, returning the raw
+    /// filtered scanline bytes described by the PNG spec (section 7).
+    pub fn image_data(&self) -> Result<Vec<u8>> {
+        let compressed: Vec<u8> = self
+            .chunks
+            .iter()
+            .filter(|c| c.chunk_type().to_string() == "IDAT")
+            .flat_map(|c| c.data().to_vec())
+            .collect();
+
+        let mut decoder = ZlibDecoder::new(compressed.as_slice());
+        let mut raw = Vec::new();
+        decoder
+            .read_to_end(&mut raw)
+            .map_err(|_| Error::from("failed to inflate IDAT data"))?;
+
+        let header = self.header()?;
+        let expected_len = expected_image_data_len(&header)?;
+        if raw.len() != expected_len {
+            return Err(Error::from(
+                "decompressed image data length does not match IHDR dimensions",
+            ));
+        }
+
+        Ok(raw)
+    }
+
+    /// Checks the whole file against the PNG structural spec, collecting every violation
+    /// instead of aborting on the first one.
+    pub fn validate(&self) -> Vec<ValidationError> {
+        let mut errors = Vec::new();
+
+        for chunk in &self.chunks {
+            if !chunk.is_crc_valid() {
+                errors.push(ValidationError::CrcMismatch(chunk.chunk_type().to_string()));
+            }
+        }
+
+        let types: Vec<String> = self.chunks.iter().map(|c| c.chunk_type().to_string()).collect();
+
+        if !types.iter().any(|t| t == "IHDR") {
+            errors.push(ValidationError::MissingIhdr);
+        } else if types.first().map(String::as_str) != Some("IHDR") {
+            errors.push(ValidationError::IhdrNotFirst);
+        }
+
+        if !types.iter().any(|t| t == "IEND") {
+            errors.push(ValidationError::MissingIend);
+        } else if types.last().map(String::as_str) != Some("IEND") {
+            errors.push(ValidationError::IendNotLast);
+        }
+        if let Some(iend) = self.chunk_by_type("IEND") {
+            if iend.length() != 0 {
+                errors.push(ValidationError::IendNotEmpty);
+            }
+        }
+
+        let plte_count = types.iter().filter(|t| t.as_str() == "PLTE").count();
+        if plte_count > 1 {
+            errors.push(ValidationError::DuplicatePlte);
+        }
+        if plte_count > 0 {
+            if let Ok(header) = self.header() {
+                // Grayscale color types (0, 4) never carry a palette (spec section 11.2.3).
+                if matches!(header.color_type(), 0 | 4) {
+                    errors.push(ValidationError::PlteNotAllowedForColorType(header.color_type()));
+                }
+            }
+        }
+
+        let plte_pos = types.iter().position(|t| t == "PLTE");
+        let idat_pos = types.iter().position(|t| t == "IDAT");
+        for ancillary in ["gAMA", "cHRM"] {
+            if let Some(pos) = types.iter().position(|t| t == ancillary) {
+                if plte_pos.is_some_and(|p| pos > p) {
+                    errors.push(ValidationError::MisorderedChunk {
+                        chunk_type: ancillary.to_string(),
+                        must_precede: "PLTE".to_string(),
+                    });
+                }
+                if idat_pos.is_some_and(|i| pos > i) {
+                    errors.push(ValidationError::MisorderedChunk {
+                        chunk_type: ancillary.to_string(),
+                        must_precede: "IDAT".to_string(),
+                    });
+                }
+            }
+        }
+
+        for chunk in &self.chunks {
+            let chunk_type = chunk.chunk_type().to_string();
+            if chunk.chunk_type().is_critical() && chunk_type != "IDAT" && chunk_type != "PLTE" {
+                let count = types.iter().filter(|t| **t == chunk_type).count();
+                let already_reported = errors
+                    .iter()
+                    .any(|e| matches!(e, ValidationError::DuplicateCriticalChunk(t) if *t == chunk_type));
+                if count > 1 && !already_reported {
+                    errors.push(ValidationError::DuplicateCriticalChunk(chunk_type));
+                }
+            }
+        }
+
+        let idat_positions: Vec<usize> = types
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| t.as_str() == "IDAT")
+            .map(|(i, _)| i)
+            .collect();
+        if let (Some(&first), Some(&last)) = (idat_positions.first(), idat_positions.last()) {
+            if last - first + 1 != idat_positions.len() {
+                errors.push(ValidationError::NonContiguousIdat);
+            }
+        }
+
+        errors
+    }
+}
+
+fn expected_image_data_len(header: &Header) -> Result<usize> {
+    if header.interlace_method() != 0 {
+        return Err(Error::from("interlaced images are not supported"));
+    }
+    let channels = header.channels()? as usize;
+    let bits_per_pixel = channels * header.bit_depth() as usize;
+    let bytes_per_row = (header.width() as usize * bits_per_pixel + 7) / 8;
+    // Each scanline is prefixed with a one-byte filter type (spec section 7.2).
+    Ok((bytes_per_row + 1) * header.height() as usize)
+}
+
+impl TryFrom<&[u8]> for Png {
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < 8 || bytes[0..8] != Self::STANDARD_HEADER {
+            return Err(Box::from(PngErr::InvalidSignature));
+        }
+
+        let mut chunks = Vec::new();
+        let mut offset = 8usize;
+        while offset < bytes.len() {
+            if offset + 8 > bytes.len() {
+                return Err(Error::from("truncated chunk header"));
+            }
+            let length = u32::from_be_bytes(bytes[offset..offset + 4].try_into().unwrap()) as usize;
+            let chunk_end = offset
+                .checked_add(12)
+                .and_then(|v| v.checked_add(length))
+                .ok_or_else(|| Error::from("chunk length overflows usize"))?;
+            if chunk_end > bytes.len() {
+                return Err(Error::from("truncated chunk data"));
+            }
+
+            let chunk = Chunk::try_from(&bytes[offset..chunk_end].to_vec())?;
+            let is_iend = chunk.chunk_type().to_string() == "IEND";
+            chunks.push(chunk);
+            offset = chunk_end;
+            if is_iend {
+                break;
+            }
+        }
+
+        Ok(Png { chunks })
+    }
+}
+
+impl Display for Png {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Png {{ chunks: {} }}", self.chunks.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use crate::chunk_type::ChunkType;
+
+    fn iend_chunk() -> Chunk {
+        Chunk::new(ChunkType::from_str("IEND").unwrap(), Vec::new())
+    }
+
+    #[test]
+    fn test_png_from_reader_round_trip() {
+        let png = Png::from_chunks(vec![iend_chunk()]);
+        let bytes = png.as_bytes();
+        let mut cursor = std::io::Cursor::new(bytes);
+
+        let parsed = Png::from_reader(&mut cursor).unwrap();
+
+        assert_eq!(parsed.chunks().len(), 1);
+        assert_eq!(parsed.chunks()[0].chunk_type().to_string(), "IEND");
+    }
+
+    #[test]
+    fn test_png_from_reader_eof_immediately_after_signature() {
+        let mut cursor = std::io::Cursor::new(Png::STANDARD_HEADER.to_vec());
+
+        assert!(Png::from_reader(&mut cursor).is_err());
+    }
+
+    #[test]
+    fn test_png_from_reader_rejects_bad_signature() {
+        let mut cursor = std::io::Cursor::new(vec![0u8; 8]);
+
+        assert!(Png::from_reader(&mut cursor).is_err());
+    }
+
+    fn ihdr_chunk(width: u32, height: u32, bit_depth: u8, color_type: u8) -> Chunk {
+        let data: Vec<u8> = width
+            .to_be_bytes()
+            .iter()
+            .chain(height.to_be_bytes().iter())
+            .chain([bit_depth, color_type, 0, 0, 0].iter())
+            .copied()
+            .collect();
+        Chunk::new(ChunkType::from_str("IHDR").unwrap(), data)
+    }
+
+    fn deflate(raw: &[u8]) -> Vec<u8> {
+        use std::io::Write;
+        let mut encoder = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(raw).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    /// A single 1x1 grayscale, 8-bit scanline: a filter-type byte followed by one pixel byte.
+    fn one_pixel_scanline() -> Vec<u8> {
+        vec![0, 0xAB]
+    }
+
+    #[test]
+    fn test_png_header_parses_ihdr() {
+        let png = Png::from_chunks(vec![ihdr_chunk(1, 1, 8, 0), iend_chunk()]);
+
+        let header = png.header().unwrap();
+
+        assert_eq!(header.width(), 1);
+        assert_eq!(header.height(), 1);
+        assert_eq!(header.bit_depth(), 8);
+        assert_eq!(header.color_type(), 0);
+    }
+
+    #[test]
+    fn test_png_header_missing_ihdr_is_an_error() {
+        let png = Png::from_chunks(vec![iend_chunk()]);
+
+        assert!(png.header().is_err());
+    }
+
+    #[test]
+    fn test_png_image_data_inflates_idat_chunks() {
+        let raw = one_pixel_scanline();
+        let idat = Chunk::new(ChunkType::from_str("IDAT").unwrap(), deflate(&raw));
+        let png = Png::from_chunks(vec![ihdr_chunk(1, 1, 8, 0), idat, iend_chunk()]);
+
+        let image_data = png.image_data().unwrap();
+
+        assert_eq!(image_data, raw);
+    }
+
+    #[test]
+    fn test_png_image_data_concatenates_multiple_idat_chunks() {
+        let raw = one_pixel_scanline();
+        let compressed = deflate(&raw);
+        let split = compressed.len() / 2;
+        let idat_1 = Chunk::new(ChunkType::from_str("IDAT").unwrap(), compressed[..split].to_vec());
+        let idat_2 = Chunk::new(ChunkType::from_str("IDAT").unwrap(), compressed[split..].to_vec());
+        let png = Png::from_chunks(vec![ihdr_chunk(1, 1, 8, 0), idat_1, idat_2, iend_chunk()]);
+
+        let image_data = png.image_data().unwrap();
+
+        assert_eq!(image_data, raw);
+    }
+
+    #[test]
+    fn test_png_image_data_rejects_length_mismatch_with_ihdr() {
+        // IHDR claims a 2x2 image but the IDAT only inflates to a single 1x1 scanline.
+        let raw = one_pixel_scanline();
+        let idat = Chunk::new(ChunkType::from_str("IDAT").unwrap(), deflate(&raw));
+        let png = Png::from_chunks(vec![ihdr_chunk(2, 2, 8, 0), idat, iend_chunk()]);
+
+        assert!(png.image_data().is_err());
+    }
+
+    fn named_chunk(chunk_type: &str, data: Vec<u8>) -> Chunk {
+        Chunk::new(ChunkType::from_str(chunk_type).unwrap(), data)
+    }
+
+    #[test]
+    fn test_validate_accepts_a_well_formed_png() {
+        let raw = one_pixel_scanline();
+        let idat = named_chunk("IDAT", deflate(&raw));
+        let png = Png::from_chunks(vec![ihdr_chunk(1, 1, 8, 0), idat, iend_chunk()]);
+
+        assert_eq!(png.validate(), Vec::new());
+    }
+
+    #[test]
+    fn test_validate_missing_ihdr() {
+        let png = Png::from_chunks(vec![iend_chunk()]);
+
+        assert!(png.validate().contains(&ValidationError::MissingIhdr));
+    }
+
+    #[test]
+    fn test_validate_duplicate_ihdr_reported_as_duplicate_critical_chunk() {
+        let png = Png::from_chunks(vec![ihdr_chunk(1, 1, 8, 0), ihdr_chunk(1, 1, 8, 0), iend_chunk()]);
+
+        assert!(png
+            .validate()
+            .contains(&ValidationError::DuplicateCriticalChunk("IHDR".to_string())));
+    }
+
+    #[test]
+    fn test_validate_ihdr_not_first() {
+        let png = Png::from_chunks(vec![named_chunk("gAMA", vec![0, 0, 0, 0]), ihdr_chunk(1, 1, 8, 0), iend_chunk()]);
+
+        assert!(png.validate().contains(&ValidationError::IhdrNotFirst));
+    }
+
+    #[test]
+    fn test_validate_missing_iend() {
+        let png = Png::from_chunks(vec![ihdr_chunk(1, 1, 8, 0)]);
+
+        assert!(png.validate().contains(&ValidationError::MissingIend));
+    }
+
+    #[test]
+    fn test_validate_iend_not_last() {
+        let png = Png::from_chunks(vec![ihdr_chunk(1, 1, 8, 0), iend_chunk(), named_chunk("gAMA", vec![0, 0, 0, 0])]);
+
+        assert!(png.validate().contains(&ValidationError::IendNotLast));
+    }
+
+    #[test]
+    fn test_validate_iend_not_empty() {
+        let png = Png::from_chunks(vec![ihdr_chunk(1, 1, 8, 0), named_chunk("IEND", vec![1])]);
+
+        assert!(png.validate().contains(&ValidationError::IendNotEmpty));
+    }
+
+    #[test]
+    fn test_validate_duplicate_plte() {
+        let png = Png::from_chunks(vec![
+            ihdr_chunk(1, 1, 8, 2),
+            named_chunk("PLTE", vec![0, 0, 0]),
+            named_chunk("PLTE", vec![0, 0, 0]),
+            iend_chunk(),
+        ]);
+
+        // A duplicate PLTE is critical-chunk-shaped too, but it must be reported once, as
+        // `DuplicatePlte`, not also as a generic `DuplicateCriticalChunk("PLTE")`.
+        assert_eq!(png.validate(), vec![ValidationError::DuplicatePlte]);
+    }
+
+    #[test]
+    fn test_validate_plte_not_allowed_for_grayscale() {
+        let png = Png::from_chunks(vec![
+            ihdr_chunk(1, 1, 8, 0),
+            named_chunk("PLTE", vec![0, 0, 0]),
+            iend_chunk(),
+        ]);
+
+        assert!(png
+            .validate()
+            .contains(&ValidationError::PlteNotAllowedForColorType(0)));
+    }
+
+    #[test]
+    fn test_validate_ancillary_must_precede_plte() {
+        let png = Png::from_chunks(vec![
+            ihdr_chunk(1, 1, 8, 2),
+            named_chunk("PLTE", vec![0, 0, 0]),
+            named_chunk("gAMA", vec![0, 0, 0, 0]),
+            iend_chunk(),
+        ]);
+
+        assert!(png.validate().contains(&ValidationError::MisorderedChunk {
+            chunk_type: "gAMA".to_string(),
+            must_precede: "PLTE".to_string(),
+        }));
+    }
+
+    #[test]
+    fn test_validate_noncontiguous_idat() {
+        let raw = one_pixel_scanline();
+        let compressed = deflate(&raw);
+        let split = compressed.len() / 2;
+        let png = Png::from_chunks(vec![
+            ihdr_chunk(1, 1, 8, 0),
+            named_chunk("IDAT", compressed[..split].to_vec()),
+            named_chunk("gIFx", vec![0]),
+            named_chunk("IDAT", compressed[split..].to_vec()),
+            iend_chunk(),
+        ]);
+
+        assert!(png.validate().contains(&ValidationError::NonContiguousIdat));
+    }
+
+    #[test]
+    fn test_validate_crc_mismatch() {
+        let mut bad_ihdr_bytes = ihdr_chunk(1, 1, 8, 0).as_bytes();
+        let last = bad_ihdr_bytes.len() - 1;
+        bad_ihdr_bytes[last] ^= 0xFF;
+        let mut cursor = std::io::Cursor::new(bad_ihdr_bytes);
+        let bad_ihdr = Chunk::from_reader_lenient(&mut cursor).unwrap();
+
+        let png = Png::from_chunks(vec![bad_ihdr, iend_chunk()]);
+
+        assert!(png
+            .validate()
+            .contains(&ValidationError::CrcMismatch("IHDR".to_string())));
+    }
+}
@@ -0,0 +1,50 @@
+#![allow(unused_imports)]
+
+use std::fmt::{Display, Formatter};
+
+/// A single violation of the PNG structural spec, as found by `Png::validate`.
+///
+/// Unlike a CRC mismatch caught during parsing, these are collected rather than aborting on the
+/// first one so `validate` can report every problem in a file in one pass.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    MissingIhdr,
+    IhdrNotFirst,
+    MissingIend,
+    IendNotLast,
+    IendNotEmpty,
+    DuplicatePlte,
+    PlteNotAllowedForColorType(u8),
+    MisorderedChunk { chunk_type: String, must_precede: String },
+    DuplicateCriticalChunk(String),
+    NonContiguousIdat,
+    CrcMismatch(String),
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationError::MissingIhdr => write!(f, "missing IHDR chunk"),
+            ValidationError::IhdrNotFirst => write!(f, "IHDR is not the first chunk"),
+            ValidationError::MissingIend => write!(f, "missing IEND chunk"),
+            ValidationError::IendNotLast => write!(f, "IEND is not the last chunk"),
+            ValidationError::IendNotEmpty => write!(f, "IEND chunk data length is not zero"),
+            ValidationError::DuplicatePlte => write!(f, "more than one PLTE chunk"),
+            ValidationError::PlteNotAllowedForColorType(color_type) => {
+                write!(f, "PLTE chunk not allowed for color type {}", color_type)
+            }
+            ValidationError::MisorderedChunk { chunk_type, must_precede } => {
+                write!(f, "{} chunk must appear before {}", chunk_type, must_precede)
+            }
+            ValidationError::DuplicateCriticalChunk(chunk_type) => {
+                write!(f, "duplicate critical chunk {}", chunk_type)
+            }
+            ValidationError::NonContiguousIdat => write!(f, "IDAT chunks are not contiguous"),
+            ValidationError::CrcMismatch(chunk_type) => {
+                write!(f, "CRC mismatch in {} chunk", chunk_type)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}